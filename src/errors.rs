@@ -0,0 +1,62 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Errors {
+    UnexpectedCharacter { character: char, line: i32 },
+    MalformedNumber { literal: String, line: i32 },
+    MalformedEscapeSequence { sequence: String, line: i32 },
+    UnterminatedString { line: i32 },
+    UnterminatedComment { line: i32 },
+    ParseError { message: String, line: i32 },
+}
+
+impl Errors {
+    pub fn unexpected_character(character: char, line: i32) -> Errors {
+        Errors::UnexpectedCharacter { character, line }
+    }
+
+    pub fn malformed_number(literal: String, line: i32) -> Errors {
+        Errors::MalformedNumber { literal, line }
+    }
+
+    pub fn malformed_escape_sequence(sequence: String, line: i32) -> Errors {
+        Errors::MalformedEscapeSequence { sequence, line }
+    }
+
+    pub fn unterminated_string(line: i32) -> Errors {
+        Errors::UnterminatedString { line }
+    }
+
+    pub fn unterminated_comment(line: i32) -> Errors {
+        Errors::UnterminatedComment { line }
+    }
+
+    pub fn parse_error(message: String, line: i32) -> Errors {
+        Errors::ParseError { message, line }
+    }
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Errors::UnexpectedCharacter { character, line } => {
+                write!(f, "[line {}] Error: Unexpected character '{}'", line, character)
+            }
+            Errors::MalformedNumber { literal, line } => {
+                write!(f, "[line {}] Error: Malformed number literal '{}'", line, literal)
+            }
+            Errors::MalformedEscapeSequence { sequence, line } => {
+                write!(f, "[line {}] Error: Malformed escape sequence '{}'", line, sequence)
+            }
+            Errors::UnterminatedString { line } => {
+                write!(f, "[line {}] Error: Unterminated string", line)
+            }
+            Errors::UnterminatedComment { line } => {
+                write!(f, "[line {}] Error: Unterminated comment", line)
+            }
+            Errors::ParseError { message, line } => {
+                write!(f, "[line {}] Error: {}", line, message)
+            }
+        }
+    }
+}
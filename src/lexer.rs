@@ -1,5 +1,3 @@
-use core::panic;
-
 use crate::errors::Errors;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -29,6 +27,7 @@ pub enum TokenType {
 
     // Literals
     Number,
+    Float,
     Identifier,
     String,
 
@@ -62,41 +61,69 @@ pub enum TokenType {
     EOF,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Position {
+    pub fn new(line: u32, column: u32) -> Position {
+        Position { line, column }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Span {
+        Span { start, end }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Token {
     pub token: TokenType,
     pub lexeme: String,
     pub literal: String,
-    pub line: i32,
+    pub span: Span,
 }
 
 impl Token {
-    pub fn new(token: TokenType, lexeme: String, literal: String, line: i32) -> Token {
+    pub fn new(token: TokenType, lexeme: String, literal: String, span: Span) -> Token {
         Token {
             token,
             lexeme,
             literal,
-            line,
+            span,
         }
     }
 }
 
 pub struct Scanner {
-    source: String,
+    source: Vec<char>,
     tokens: Vec<Token>,
-    start: i32,
-    current: i32,
+    start: usize,
+    current: usize,
     line: i32,
+    column: u32,
+    start_position: Position,
 }
 
 impl Scanner {
     pub fn new(source: String) -> Scanner {
         Scanner {
-            source,
+            source: source.chars().collect(),
             tokens: Vec::new(),
             start: 0,
             current: 0,
             line: 1,
+            column: 1,
+            start_position: Position::new(1, 1),
         }
     }
 
@@ -126,18 +153,71 @@ impl Scanner {
         &self.tokens
     }
 
-    pub fn scan_tokens(&mut self) {
-        while !self.is_at_end() {
+    pub fn scan_tokens(&mut self) -> Result<&Vec<Token>, Vec<Errors>> {
+        let mut errors = Vec::new();
+
+        loop {
+            match self.next_token() {
+                Ok(token) => {
+                    let is_eof = matches!(token.token, TokenType::EOF);
+                    self.tokens.push(token);
+
+                    if is_eof {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    errors.push(error);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(&self.tokens)
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn next_token(&mut self) -> Result<Token, Errors> {
+        loop {
+            if self.is_at_end() {
+                let eof_position = Position::new(self.line as u32, self.column);
+
+                return Ok(Token::new(
+                    TokenType::EOF,
+                    "".to_string(),
+                    "".to_string(),
+                    Span::new(eof_position, eof_position),
+                ));
+            }
+
             self.start = self.current;
-            let _ = self.scan_token();
+            self.start_position = Position::new(self.line as u32, self.column);
+
+            let tokens_before = self.tokens.len();
+
+            if let Err(error) = self.scan_token() {
+                self.synchronize();
+                return Err(error);
+            }
+
+            if self.tokens.len() > tokens_before {
+                return Ok(self.tokens.pop().unwrap());
+            }
         }
+    }
 
-        self.tokens.push(Token::new(
-            TokenType::EOF,
-            "".to_string(),
-            "".to_string(),
-            self.line,
-        ));
+    // Every lexical error is only raised after the offending token's full
+    // span (the bad character, the malformed number, the string up to its
+    // real closing quote, ...) has already been consumed, so scanning can
+    // resume from `self.current` as-is. This only guards against an error
+    // path that made no progress, so callers that keep calling `next_token`
+    // after an error (`scan_tokens`, `TokenStream`) can't spin.
+    fn synchronize(&mut self) {
+        if self.current == self.start && !self.is_at_end() {
+            self.advance();
+        }
     }
 
     fn scan_token(&mut self) -> Result<(), Errors> {
@@ -207,7 +287,7 @@ impl Scanner {
             }
             '/' => {
                 if self.is_match('*') {
-                    while self.peek() != '*' && self.peek_next() != '/' && !self.is_at_end() {
+                    while !(self.is_at_end() || (self.peek() == '*' && self.peek_next() == '/')) {
                         if self.peek() == '\n' {
                             self.line += 1;
                         }
@@ -216,7 +296,7 @@ impl Scanner {
                     }
 
                     if self.is_at_end() {
-                        panic!("Unterminated comment");
+                        return Err(Errors::unterminated_comment(self.line));
                     }
 
                     self.advance();
@@ -230,14 +310,14 @@ impl Scanner {
                     let _ = self.add_token(TokenType::FnTypeAssigner);
                 }
             }
-            '"' => self.make_string(),
+            '"' => self.make_string()?,
             c => {
                 if c.is_digit(10) {
                     return self.make_number();
                 } else if c.is_alphanumeric() {
                     return self.make_identifier();
                 } else {
-                    Errors::unexpected_character(c, self.line);
+                    return Err(Errors::unexpected_character(c, self.line));
                 }
             }
         };
@@ -245,14 +325,22 @@ impl Scanner {
     }
 
     fn advance(&mut self) -> char {
-        let current = self.current.clone();
+        let current = self.current;
         self.current += 1;
 
-        self.source.chars().nth(current as usize).unwrap()
+        let character = self.source[current];
+
+        if character == '\n' {
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        character
     }
 
     fn is_at_end(&self) -> bool {
-        self.current >= self.source.len().try_into().unwrap()
+        self.current >= self.source.len()
     }
 
     fn add_token(&mut self, token: TokenType) -> Result<(), Errors> {
@@ -266,14 +354,14 @@ impl Scanner {
         token: TokenType,
         literal: Option<String>,
     ) -> Result<(), Errors> {
-        let text = self.source[self.start.try_into().unwrap()..self.current.try_into().unwrap()]
-            .to_string();
+        let text = self.source[self.start..self.current].iter().collect::<String>();
+        let span = Span::new(self.start_position, Position::new(self.line as u32, self.column));
 
         self.tokens.push(Token::new(
             token,
             text,
             literal.unwrap_or("".to_string()),
-            self.line,
+            span,
         ));
 
         Ok(())
@@ -284,11 +372,11 @@ impl Scanner {
             return false;
         }
 
-        if self.source.chars().nth(self.current as usize).unwrap() != expected {
+        if self.source[self.current] != expected {
             return false;
         }
 
-        self.current += 1;
+        self.advance();
         true
     }
 
@@ -297,56 +385,163 @@ impl Scanner {
             return '\0';
         }
 
-        self.source.chars().nth(self.current as usize).unwrap()
+        self.source[self.current]
     }
 
     fn peek_next(&self) -> char {
-        if self.current + 1 >= self.source.len().try_into().unwrap() {
+        if self.current + 1 >= self.source.len() {
             return '\0';
         }
 
-        self.source
-            .chars()
-            .nth((self.current + 1) as usize)
-            .unwrap()
+        self.source[self.current + 1]
     }
 
-    fn make_string(&mut self) {
+    fn make_string(&mut self) -> Result<(), Errors> {
+        let mut value = String::new();
+        let mut error = None;
+
         while self.peek() != '"' && !self.is_at_end() {
-            if self.peek() == '\n' {
+            let character = self.advance();
+
+            if character == '\n' {
                 self.line += 1;
             }
 
-            self.advance();
+            if character == '\\' {
+                match self.make_escape_sequence() {
+                    Ok(decoded) => value.push(decoded),
+                    Err(escape_error) => {
+                        error.get_or_insert(escape_error);
+                    }
+                };
+            } else {
+                value.push(character);
+            }
         }
 
         if self.is_at_end() {
-            panic!("Unterminated string");
+            return Err(error.unwrap_or_else(|| Errors::unterminated_string(self.line)));
         }
 
+        // Consume the real closing quote even when an earlier escape was
+        // malformed, so the scanner resumes after the string instead of
+        // reinterpreting this quote as the start of a new one.
         self.advance();
 
-        let value = self.source[(self.start as usize) + 1..(self.current as usize) - 1].to_string();
+        match error {
+            Some(error) => Err(error),
+            None => self.add_token_literal(TokenType::String, Some(value)),
+        }
+    }
+
+    fn make_escape_sequence(&mut self) -> Result<char, Errors> {
+        if self.is_at_end() {
+            return Err(Errors::unterminated_string(self.line));
+        }
 
-        let _ = self.add_token_literal(TokenType::String, Some(value));
+        let escaped = self.advance();
+
+        match escaped {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            '0' => Ok('\0'),
+            'u' => self.make_unicode_escape(),
+            other => Err(Errors::malformed_escape_sequence(
+                format!("\\{}", other),
+                self.line,
+            )),
+        }
+    }
+
+    fn make_unicode_escape(&mut self) -> Result<char, Errors> {
+        if self.peek() != '{' {
+            return Err(Errors::malformed_escape_sequence("\\u".to_string(), self.line));
+        }
+
+        self.advance();
+
+        let digits_start = self.current;
+
+        while self.peek() != '}' && !self.is_at_end() {
+            self.advance();
+        }
+
+        let digits = self.source[digits_start..self.current]
+            .iter()
+            .collect::<String>();
+
+        if self.is_at_end() {
+            return Err(Errors::malformed_escape_sequence(
+                format!("\\u{{{}", digits),
+                self.line,
+            ));
+        }
+
+        self.advance();
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| {
+                Errors::malformed_escape_sequence(format!("\\u{{{}}}", digits), self.line)
+            })
     }
 
     fn make_number(&mut self) -> Result<(), Errors> {
+        if self.source[self.start] == '0' && matches!(self.peek(), 'x' | 'b' | 'o') {
+            let base = match self.advance() {
+                'x' => 16,
+                'b' => 2,
+                'o' => 8,
+                _ => unreachable!(),
+            };
+
+            let digits_start = self.current;
+
+            while is_in_base(self.peek(), base) {
+                self.advance();
+            }
+
+            if self.current == digits_start || self.peek().is_alphanumeric() {
+                while self.peek().is_alphanumeric() {
+                    self.advance();
+                }
+
+                let value = self.source[self.start..self.current].iter().collect::<String>();
+
+                return Err(Errors::malformed_number(value, self.line));
+            }
+
+            let value = self.source[self.start..self.current].iter().collect::<String>();
+
+            return self.add_token_literal(TokenType::Number, Some(value));
+        }
+
         while self.peek().is_digit(10) {
             self.advance();
         }
 
+        let mut is_float = false;
+
         if self.peek() == '.' && self.peek_next().is_digit(10) {
+            is_float = true;
             self.advance();
-        }
 
-        while self.peek().is_digit(10) {
-            self.advance();
+            while self.peek().is_digit(10) {
+                self.advance();
+            }
         }
 
-        let value = self.source[(self.start as usize)..(self.current as usize)].to_string();
+        let value = self.source[self.start..self.current].iter().collect::<String>();
 
-        self.add_token_literal(TokenType::Number, Some(value))
+        if is_float {
+            self.add_token_literal(TokenType::Float, Some(value))
+        } else {
+            self.add_token_literal(TokenType::Number, Some(value))
+        }
     }
 
     fn make_identifier(&mut self) -> Result<(), Errors> {
@@ -354,7 +549,7 @@ impl Scanner {
             self.advance();
         }
 
-        let value = self.source[(self.start as usize)..(self.current as usize)].to_string();
+        let value = self.source[self.start..self.current].iter().collect::<String>();
 
         if let Some(keyword) = self.get_keyword(value.clone()) {
             return self.add_token(keyword);
@@ -363,3 +558,47 @@ impl Scanner {
         return self.add_token_literal(TokenType::Identifier, Some(value));
     }
 }
+
+fn is_in_base(c: char, base: u32) -> bool {
+    match base {
+        2 => matches!(c, '0' | '1'),
+        8 => matches!(c, '0'..='7'),
+        16 => c.is_ascii_hexdigit(),
+        _ => c.is_digit(base),
+    }
+}
+
+pub struct TokenStream {
+    scanner: Scanner,
+    finished: bool,
+}
+
+impl TokenStream {
+    pub fn new(source: String) -> TokenStream {
+        TokenStream {
+            scanner: Scanner::new(source),
+            finished: false,
+        }
+    }
+}
+
+impl Iterator for TokenStream {
+    type Item = Result<Token, Errors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.scanner.next_token() {
+            Ok(token) => {
+                if matches!(token.token, TokenType::EOF) {
+                    self.finished = true;
+                }
+
+                Some(Ok(token))
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
@@ -2,15 +2,53 @@ pub mod errors;
 pub mod lexer;
 pub mod parser;
 
-use lexer::Scanner;
+use std::io::{self, Write};
+
+use lexer::{Scanner, TokenStream};
+use parser::Parser;
 
 fn main() {
     let text = r#"5 + 5"#;
 
     let mut scanner = Scanner::new(text.to_string());
-    scanner.scan_tokens();
 
-    let tokens = scanner.get_tokens();
+    match scanner.scan_tokens() {
+        Ok(tokens) => {
+            let mut parser = Parser::new(tokens.clone());
+
+            match parser.parse() {
+                Ok(expr) => println!("{:?}", expr),
+                Err(error) => eprintln!("{}", error),
+            }
+        }
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error);
+            }
+        }
+    }
+
+    repl();
+}
+
+fn repl() {
+    let stdin = io::stdin();
+
+    loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
 
-    println!("{:?}", tokens);
+        for result in TokenStream::new(line) {
+            match result {
+                Ok(token) => println!("{:?}", token),
+                Err(error) => eprintln!("{}", error),
+            }
+        }
+    }
 }
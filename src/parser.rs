@@ -1,28 +1,221 @@
-use crate::lexer::Token;
+use crate::errors::Errors;
+use crate::lexer::{Token, TokenType};
 
-pub struct Expr {
-    left: Box<Expr>,
-    operator: Token,
-    right: Box<Expr>,
+#[derive(Debug, Clone)]
+pub enum Literal {
+    Number(String),
+    Float(String),
+    String(String),
+    Bool(bool),
+    Nil,
+    Identifier(String),
 }
 
-impl Expr {
-    pub fn new(left: Box<Expr>, operator: Token, right: Box<Expr>) -> Expr {
-        Expr {
-            left,
-            operator,
-            right,
-        }
-    }
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Binary {
+        left: Box<Expr>,
+        op: Token,
+        right: Box<Expr>,
+    },
+    Unary {
+        op: Token,
+        operand: Box<Expr>,
+    },
+    Grouping(Box<Expr>),
+    Literal(Literal),
 }
 
 pub struct Parser {
     tokens: Vec<Token>,
-    current: i32,
+    current: usize,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Parser {
         Parser { tokens, current: 0 }
     }
+
+    pub fn parse(&mut self) -> Result<Expr, Errors> {
+        self.expression()
+    }
+
+    fn expression(&mut self) -> Result<Expr, Errors> {
+        self.equality()
+    }
+
+    fn equality(&mut self) -> Result<Expr, Errors> {
+        let mut expr = self.comparison()?;
+
+        while self.match_token(&[TokenType::EqualEqual, TokenType::BangEqual]) {
+            let op = self.previous().clone();
+            let right = self.comparison()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, Errors> {
+        let mut expr = self.term()?;
+
+        while self.match_token(&[
+            TokenType::Less,
+            TokenType::LessEqual,
+            TokenType::Greater,
+            TokenType::GreaterEqual,
+        ]) {
+            let op = self.previous().clone();
+            let right = self.term()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expr, Errors> {
+        let mut expr = self.factor()?;
+
+        while self.match_token(&[TokenType::Plus, TokenType::Minus]) {
+            let op = self.previous().clone();
+            let right = self.factor()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn factor(&mut self) -> Result<Expr, Errors> {
+        let mut expr = self.unary()?;
+
+        while self.match_token(&[TokenType::Star, TokenType::Slash]) {
+            let op = self.previous().clone();
+            let right = self.unary()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                op,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Errors> {
+        if self.match_token(&[TokenType::Bang, TokenType::Minus]) {
+            let op = self.previous().clone();
+            let operand = self.unary()?;
+
+            return Ok(Expr::Unary {
+                op,
+                operand: Box::new(operand),
+            });
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, Errors> {
+        if self.match_token(&[TokenType::Number]) {
+            return Ok(Expr::Literal(Literal::Number(self.previous().literal.clone())));
+        }
+
+        if self.match_token(&[TokenType::Float]) {
+            return Ok(Expr::Literal(Literal::Float(self.previous().literal.clone())));
+        }
+
+        if self.match_token(&[TokenType::String]) {
+            return Ok(Expr::Literal(Literal::String(self.previous().literal.clone())));
+        }
+
+        if self.match_token(&[TokenType::True]) {
+            return Ok(Expr::Literal(Literal::Bool(true)));
+        }
+
+        if self.match_token(&[TokenType::False]) {
+            return Ok(Expr::Literal(Literal::Bool(false)));
+        }
+
+        if self.match_token(&[TokenType::Nil]) {
+            return Ok(Expr::Literal(Literal::Nil));
+        }
+
+        if self.match_token(&[TokenType::Identifier]) {
+            return Ok(Expr::Literal(Literal::Identifier(self.previous().lexeme.clone())));
+        }
+
+        if self.match_token(&[TokenType::LeftParen]) {
+            let expr = self.expression()?;
+            self.consume(TokenType::RightParen, "Expect ')' after expression.")?;
+
+            return Ok(Expr::Grouping(Box::new(expr)));
+        }
+
+        Err(Errors::parse_error(
+            "Expect expression.".to_string(),
+            self.peek().span.start.line as i32,
+        ))
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.current]
+    }
+
+    fn previous(&self) -> &Token {
+        &self.tokens[self.current - 1]
+    }
+
+    fn is_at_end(&self) -> bool {
+        matches!(self.peek().token, TokenType::EOF)
+    }
+
+    fn advance(&mut self) -> &Token {
+        if !self.is_at_end() {
+            self.current += 1;
+        }
+
+        self.previous()
+    }
+
+    fn check(&self, token: &TokenType) -> bool {
+        if self.is_at_end() {
+            return false;
+        }
+
+        &self.peek().token == token
+    }
+
+    fn match_token(&mut self, tokens: &[TokenType]) -> bool {
+        for token in tokens {
+            if self.check(token) {
+                self.advance();
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn consume(&mut self, token: TokenType, message: &str) -> Result<&Token, Errors> {
+        if self.check(&token) {
+            return Ok(self.advance());
+        }
+
+        Err(Errors::parse_error(
+            message.to_string(),
+            self.peek().span.start.line as i32,
+        ))
+    }
 }